@@ -1,44 +1,149 @@
-use std::path::{PathBuf};
-use std::fs;
-use std::io::{self, Write};
+use cosmic::cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry};
+use serde::{Deserialize, Serialize};
 
 pub const CONFIG_VERSION: u64 = 1;
 
-fn config_dir() -> Option<PathBuf> {
-	if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
-		return Some(PathBuf::from(xdg).join("cosmic-ext-applet-ressources-monitor"));
-	}
-	if let Ok(home) = std::env::var("HOME") {
-		return Some(PathBuf::from(home).join(".config").join("cosmic-ext-applet-ressources-monitor"));
-	}
-	None
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
 }
 
-fn selected_sensor_path() -> Option<PathBuf> {
-	config_dir().map(|d| d.join("selected_sensor.txt"))
+impl Default for TemperatureUnit {
+    fn default() -> Self {
+        Self::Celsius
+    }
 }
 
-pub fn load_selected_sensor() -> Option<String> {
-	let path = selected_sensor_path()?;
-	if path.exists() {
-		if let Ok(s) = fs::read_to_string(path) {
-			let t = s.trim().to_string();
-			if t.is_empty() { None } else { Some(t) }
-		} else {
-			None
-		}
-	} else {
-		None
-	}
+/// Persisted applet settings, loaded and saved through `cosmic-config`.
+///
+/// Replaces the old flat `selected_sensor.txt` file: every user-facing
+/// setting now lives on this struct so it can be changed live and survive
+/// upgrades via `CONFIG_VERSION`.
+#[derive(Debug, Clone, PartialEq, CosmicConfigEntry)]
+#[version = 1]
+pub struct Config {
+    /// How often `Window` polls `sysinfo` for fresh metrics, in milliseconds.
+    pub refresh_interval_ms: u64,
+    /// Label of the temperature sensor selected by the user, if any.
+    pub selected_sensor: Option<String>,
+    /// Unit used when displaying temperatures.
+    pub temperature_unit: TemperatureUnit,
+    pub show_cpu: bool,
+    pub show_freq: bool,
+    pub show_temp: bool,
+    pub show_ram: bool,
+    /// When `true` the panel shows the average CPU usage across all cores;
+    /// when `false` it shows the single busiest core instead.
+    pub show_average_cpu: bool,
+    /// Number of the most recent samples shown in the popup's history
+    /// graphs; lets the user "zoom" the visible time span.
+    pub history_window: usize,
+    pub show_network: bool,
+    /// Interface name globs to show; empty means "all interfaces".
+    pub net_interface_include: Vec<String>,
+    /// Interface name globs to always hide, e.g. `virbr*`/`veth*`.
+    pub net_interface_exclude: Vec<String>,
+    pub show_disk: bool,
+    /// Mount point of the disk summarized in the panel; `None` uses `/`.
+    pub summary_mount: Option<String>,
+    /// Mount point globs to hide from the disk list, e.g. pseudo or
+    /// removable filesystems.
+    pub disk_mount_exclude: Vec<String>,
 }
 
-pub fn save_selected_sensor(label: &str) -> io::Result<()> {
-	if let Some(dir) = config_dir() {
-		fs::create_dir_all(&dir)?;
-		if let Some(path) = selected_sensor_path() {
-			let mut f = fs::File::create(path)?;
-			f.write_all(label.as_bytes())?;
-		}
-	}
-	Ok(())
+/// Matches `name` against a shell-style glob containing only `*` wildcards.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    if pattern.is_empty() {
+        return name.is_empty();
+    }
+
+    let mut parts = pattern.split('*').peekable();
+    let anchored_start = !pattern.starts_with('*');
+    let anchored_end = !pattern.ends_with('*');
+
+    let mut rest = name;
+    let mut first = true;
+    while let Some(part) = parts.next() {
+        if part.is_empty() {
+            first = false;
+            continue;
+        }
+        let pos = if parts.peek().is_none() && anchored_end {
+            // The final segment must sit at the very end of the name.
+            if !rest.ends_with(part) {
+                return false;
+            }
+            rest.len() - part.len()
+        } else {
+            match rest.find(part) {
+                Some(pos) => pos,
+                None => return false,
+            }
+        };
+        if first && anchored_start && pos != 0 {
+            return false;
+        }
+        rest = &rest[pos + part.len()..];
+        first = false;
+    }
+    true
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            refresh_interval_ms: 1000,
+            selected_sensor: None,
+            temperature_unit: TemperatureUnit::default(),
+            show_cpu: true,
+            show_freq: true,
+            show_temp: true,
+            show_ram: true,
+            show_average_cpu: true,
+            history_window: 60,
+            show_network: true,
+            net_interface_include: Vec::new(),
+            net_interface_exclude: vec![
+                "virbr*".to_string(),
+                "veth*".to_string(),
+                "docker*".to_string(),
+                "br-*".to_string(),
+                "lo".to_string(),
+            ],
+            show_disk: true,
+            summary_mount: None,
+            disk_mount_exclude: vec!["/boot*".to_string(), "/snap/*".to_string()],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::glob_match;
+
+    #[test]
+    fn empty_pattern_only_matches_empty_name() {
+        assert!(!glob_match("", "eth0"));
+        assert!(glob_match("", ""));
+    }
+
+    #[test]
+    fn exact_pattern_requires_full_match() {
+        assert!(glob_match("lo", "lo"));
+        assert!(!glob_match("lo", "lo0"));
+    }
+
+    #[test]
+    fn trailing_wildcard_matches_prefix() {
+        assert!(glob_match("virbr*", "virbr0"));
+        assert!(!glob_match("virbr*", "eth0"));
+    }
+
+    #[test]
+    fn leading_wildcard_matches_suffix() {
+        assert!(glob_match("*eth0", "my-eth0"));
+        assert!(!glob_match("*eth0", "eth0-my"));
+    }
 }
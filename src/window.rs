@@ -1,31 +1,156 @@
+use std::collections::VecDeque;
+use std::time::Instant;
+
 use cosmic::{
     app,
     applet::cosmic_panel_config::PanelAnchor,
+    cosmic_config::{self, CosmicConfigEntry},
     iced::{
-        widget::{row, text},
-        Alignment, Subscription,
+        mouse,
+        platform_specific::shell::commands::popup::{destroy_popup, get_popup},
+        widget::{
+            canvas::{self, Canvas, Frame, Geometry, Path, Stroke},
+            column, row, text,
+        },
+        window::Id,
+        Alignment, Color, Length, Limits, Rectangle, Renderer, Subscription, Theme,
     },
     widget::{autosize, button},
     Element,
 };
 
-use sysinfo::{System, SystemExt, CpuExt, ComponentExt};
+use sysinfo::{
+    ComponentExt, CpuExt, CpuRefreshKind, DiskExt, DisksExt, NetworkExt, NetworksExt, RefreshKind,
+    System, SystemExt,
+};
+
+use crate::config::{glob_match, Config, TemperatureUnit, CONFIG_VERSION};
+
+/// How many samples are kept in each rolling history buffer, regardless of
+/// how much of it the user has chosen to look at (`history_window`).
+const HISTORY_CAPACITY: usize = 300;
 
 pub struct Window {
     core: cosmic::app::Core,
+    popup: Option<Id>,
     sys: System,
-    cpu_usage: f32,    // CPU usage in percent
-    avg_freq: u64,     // Average CPU frequency in MHz
-    cpu_temp: f32,     // CPU temperature in °C
-    ram_percent: f32,  // RAM usage in percent
+    config: Config,
+    cpu_usage: f32,            // CPU usage in percent
+    avg_freq: u64,             // Average CPU frequency in MHz
+    cpu_temp: f32,             // CPU temperature in °C
+    ram_percent: f32,          // RAM usage in percent
+    per_core: Vec<(f32, u64)>, // per-core (usage percent, frequency MHz)
+    cpu_history: VecDeque<f32>,
+    ram_history: VecDeque<f32>,
+    temp_history: VecDeque<f32>,
+    net_rx_rate: f64, // bytes/sec, averaged since the previous tick
+    net_tx_rate: f64, // bytes/sec, averaged since the previous tick
+    prev_rx: u64,
+    prev_tx: u64,
+    last_instant: Instant,
+    disks: Vec<DiskInfo>,
+}
+
+#[derive(Debug, Clone)]
+struct DiskInfo {
+    mount_point: String,
+    used: u64,
+    total: u64,
+    percent: f32,
+}
+
+/// A minimal line graph over a slice of recent samples, autoscaled to the
+/// largest value currently in view (the scale never drops below 1.0).
+struct Sparkline<'a> {
+    samples: &'a [f32],
+    color: Color,
+}
+
+impl<'a> canvas::Program<Message> for Sparkline<'a> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        if self.samples.len() >= 2 {
+            let max = self
+                .samples
+                .iter()
+                .copied()
+                .fold(f32::MIN, f32::max)
+                .max(1.0);
+            let step = bounds.width / ((self.samples.len() - 1) as f32);
+
+            let points = self.samples.iter().enumerate().map(|(i, value)| {
+                let x = i as f32 * step;
+                let y = bounds.height - (value / max) * bounds.height;
+                cosmic::iced::Point::new(x, y)
+            });
+
+            let path = Path::new(|builder| {
+                let mut points = points;
+                if let Some(first) = points.next() {
+                    builder.move_to(first);
+                    for point in points {
+                        builder.line_to(point);
+                    }
+                }
+            });
+
+            frame.stroke(
+                &path,
+                Stroke::default().with_color(self.color).with_width(1.5),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+fn sparkline(samples: &[f32], color: Color) -> Element<'_, Message> {
+    Canvas::new(Sparkline { samples, color })
+        .width(Length::Fixed(260.0))
+        .height(Length::Fixed(48.0))
+        .into()
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     Tick,
+    ConfigChanged(Config),
+    TogglePopup,
 }
 
 impl Window {
+    fn push_sample(history: &mut VecDeque<f32>, value: f32) {
+        if history.len() >= HISTORY_CAPACITY {
+            history.pop_front();
+        }
+        history.push_back(value);
+    }
+
+    /// The last `window` samples of `history`, clamped to its length.
+    fn windowed_tail(history: &VecDeque<f32>, window: usize) -> Vec<f32> {
+        let window = window.min(history.len());
+        history
+            .iter()
+            .copied()
+            .skip(history.len() - window)
+            .collect()
+    }
+
+    /// The tail of `history` that fits within the user's configured zoom.
+    fn visible_history(&self, history: &VecDeque<f32>) -> Vec<f32> {
+        Self::windowed_tail(history, self.config.history_window)
+    }
+
     fn format_percent(value: f32) -> String {
         format!("{:.1}%", value)
     }
@@ -34,53 +159,249 @@ impl Window {
         format!("{} MHz", mhz)
     }
 
-    fn format_temp(temp: f32) -> String {
-        format!("{:.1} °C", temp)
+    /// Converts a Celsius reading to `unit` and formats it.
+    fn convert_temp(unit: TemperatureUnit, celsius: f32) -> String {
+        match unit {
+            TemperatureUnit::Celsius => format!("{:.1} °C", celsius),
+            TemperatureUnit::Fahrenheit => format!("{:.1} °F", celsius * 9.0 / 5.0 + 32.0),
+            TemperatureUnit::Kelvin => format!("{:.1} K", celsius + 273.15),
+        }
+    }
+
+    /// Converts a Celsius reading to the configured unit and formats it.
+    fn format_temp(&self, celsius: f32) -> String {
+        Self::convert_temp(self.config.temperature_unit, celsius)
+    }
+
+    fn format_rate(bytes_per_sec: f64) -> String {
+        const UNITS: [&str; 4] = ["B/s", "KB/s", "MB/s", "GB/s"];
+        let mut value = bytes_per_sec;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+
+    fn format_bytes(bytes: u64) -> String {
+        const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+        let mut value = bytes as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+
+    fn interface_enabled(config: &Config, name: &str) -> bool {
+        let included = config.net_interface_include.is_empty()
+            || config
+                .net_interface_include
+                .iter()
+                .any(|pat| glob_match(pat, name));
+        let excluded = config
+            .net_interface_exclude
+            .iter()
+            .any(|pat| glob_match(pat, name));
+        included && !excluded
+    }
+
+    fn is_interface_enabled(&self, name: &str) -> bool {
+        Self::interface_enabled(&self.config, name)
+    }
+
+    fn disk_enabled(config: &Config, mount_point: &str) -> bool {
+        !config
+            .disk_mount_exclude
+            .iter()
+            .any(|pat| glob_match(pat, mount_point))
+    }
+
+    fn is_disk_enabled(&self, mount_point: &str) -> bool {
+        Self::disk_enabled(&self.config, mount_point)
+    }
+
+    /// CPU usage to show on the panel: the average, or the busiest core,
+    /// depending on `show_average`.
+    fn select_cpu(show_average: bool, average: (f32, u64), per_core: &[(f32, u64)]) -> (f32, u64) {
+        if show_average || per_core.is_empty() {
+            average
+        } else {
+            *per_core.iter().max_by(|a, b| a.0.total_cmp(&b.0)).unwrap()
+        }
+    }
+
+    /// CPU usage to show on the panel: the average, or the busiest core,
+    /// depending on `Config::show_average_cpu`.
+    fn displayed_cpu(&self) -> (f32, u64) {
+        Self::select_cpu(
+            self.config.show_average_cpu,
+            (self.cpu_usage, self.avg_freq),
+            &self.per_core,
+        )
+    }
+
+    /// The `sysinfo` refresh kinds needed for the metrics the user has
+    /// enabled, so disabled subsystems are never polled.
+    fn refresh_kind(config: &Config) -> RefreshKind {
+        let mut kind = RefreshKind::new();
+        if config.show_cpu || config.show_freq {
+            kind = kind.with_cpu(CpuRefreshKind::everything());
+        }
+        if config.show_ram {
+            kind = kind.with_memory();
+        }
+        if config.show_temp {
+            kind = kind.with_components_list().with_components();
+        }
+        if config.show_network {
+            kind = kind.with_networks_list().with_networks();
+        }
+        if config.show_disk {
+            kind = kind.with_disks_list().with_disks();
+        }
+        kind
     }
 
     fn update_metrics(&mut self) {
-        self.sys.refresh_cpu();
-        self.sys.refresh_memory();
-        self.sys.refresh_components();
+        if self.config.show_cpu || self.config.show_freq {
+            self.sys.refresh_cpu();
+
+            let cpus = self.sys.cpus();
+            self.per_core = cpus
+                .iter()
+                .map(|c| (c.cpu_usage(), c.frequency()))
+                .collect();
 
-        let cpus = self.sys.cpus();
-        if !cpus.is_empty() {
-            let total_usage: f32 = cpus.iter().map(|c| c.cpu_usage()).sum::<f32>();
-            self.cpu_usage = total_usage / (cpus.len() as f32);
+            if !cpus.is_empty() {
+                let total_usage: f32 = cpus.iter().map(|c| c.cpu_usage()).sum::<f32>();
+                self.cpu_usage = total_usage / (cpus.len() as f32);
 
-            let total_freq: u64 = cpus.iter().map(|c| c.frequency() as u64).sum::<u64>();
-            self.avg_freq = total_freq / (cpus.len() as u64);
+                let total_freq: u64 = cpus.iter().map(|c| c.frequency() as u64).sum::<u64>();
+                self.avg_freq = total_freq / (cpus.len() as u64);
+            } else {
+                self.cpu_usage = 0.0;
+                self.avg_freq = 0;
+            }
         } else {
+            self.per_core.clear();
             self.cpu_usage = 0.0;
             self.avg_freq = 0;
         }
 
-        let components = self.sys.components();
-        let temps: Vec<f32> = components
-            .iter()
-            .filter(|c| {
-                let l = c.label().to_lowercase();
-                l.contains("cpu") || l.contains("package")
-            })
-            .map(|c| c.temperature())
-            .collect();
-
-        if !temps.is_empty() {
-            let sum: f32 = temps.iter().copied().sum();
-            self.cpu_temp = sum / (temps.len() as f32);
-        } else if !components.is_empty() {
-            self.cpu_temp = components.iter().map(|c| c.temperature()).fold(0.0_f32, |a, b| a.max(b));
+        if self.config.show_temp {
+            self.sys.refresh_components_list();
+            self.sys.refresh_components();
+
+            let components = self.sys.components();
+            let temps: Vec<f32> = components
+                .iter()
+                .filter(|c| {
+                    let l = c.label().to_lowercase();
+                    l.contains("cpu") || l.contains("package")
+                })
+                .map(|c| c.temperature())
+                .collect();
+
+            if !temps.is_empty() {
+                let sum: f32 = temps.iter().copied().sum();
+                self.cpu_temp = sum / (temps.len() as f32);
+            } else if !components.is_empty() {
+                self.cpu_temp = components
+                    .iter()
+                    .map(|c| c.temperature())
+                    .fold(0.0_f32, |a, b| a.max(b));
+            } else {
+                self.cpu_temp = 0.0;
+            }
         } else {
             self.cpu_temp = 0.0;
         }
 
-        let total_ram = self.sys.total_memory() as f32;
-        let used_ram = self.sys.used_memory() as f32;
-        if total_ram > 0.0 {
-            self.ram_percent = (used_ram / total_ram) * 100.0;
+        if self.config.show_ram {
+            self.sys.refresh_memory();
+
+            let total_ram = self.sys.total_memory() as f32;
+            let used_ram = self.sys.used_memory() as f32;
+            if total_ram > 0.0 {
+                self.ram_percent = (used_ram / total_ram) * 100.0;
+            } else {
+                self.ram_percent = 0.0;
+            }
         } else {
             self.ram_percent = 0.0;
         }
+
+        Self::push_sample(&mut self.cpu_history, self.cpu_usage);
+        Self::push_sample(&mut self.ram_history, self.ram_percent);
+        Self::push_sample(&mut self.temp_history, self.cpu_temp);
+
+        if self.config.show_network {
+            self.sys.refresh_networks_list();
+            self.sys.refresh_networks();
+            let mut total_rx = 0u64;
+            let mut total_tx = 0u64;
+            for (name, data) in self.sys.networks() {
+                if self.is_interface_enabled(name) {
+                    total_rx += data.total_received();
+                    total_tx += data.total_transmitted();
+                }
+            }
+
+            let elapsed = self.last_instant.elapsed().as_secs_f64().max(0.001);
+            self.net_rx_rate = total_rx.saturating_sub(self.prev_rx) as f64 / elapsed;
+            self.net_tx_rate = total_tx.saturating_sub(self.prev_tx) as f64 / elapsed;
+            self.prev_rx = total_rx;
+            self.prev_tx = total_tx;
+            self.last_instant = Instant::now();
+        } else {
+            self.net_rx_rate = 0.0;
+            self.net_tx_rate = 0.0;
+        }
+
+        if self.config.show_disk {
+            self.sys.refresh_disks_list();
+            self.sys.refresh_disks();
+
+            self.disks = self
+                .sys
+                .disks()
+                .iter()
+                .filter_map(|disk| {
+                    let mount_point = disk.mount_point().to_string_lossy().into_owned();
+                    if !self.is_disk_enabled(&mount_point) {
+                        return None;
+                    }
+                    let total = disk.total_space();
+                    let used = total.saturating_sub(disk.available_space());
+                    let percent = if total > 0 {
+                        (used as f32 / total as f32) * 100.0
+                    } else {
+                        0.0
+                    };
+                    Some(DiskInfo {
+                        mount_point,
+                        used,
+                        total,
+                        percent,
+                    })
+                })
+                .collect();
+        } else {
+            self.disks.clear();
+        }
+    }
+
+    /// The disk summarized on the panel: the user's chosen mount point, or
+    /// `/` if unset; if that mount is not listed, the first listed disk.
+    fn summary_disk(&self) -> Option<&DiskInfo> {
+        let target = self.config.summary_mount.as_deref().unwrap_or("/");
+        self.disks
+            .iter()
+            .find(|disk| disk.mount_point == target)
+            .or_else(|| self.disks.first())
     }
 }
 
@@ -94,18 +415,48 @@ impl cosmic::Application for Window {
         core: app::Core,
         _flags: Self::Flags,
     ) -> (Self, cosmic::iced::Task<app::Message<Self::Message>>) {
-        let mut sys = System::new_all();
-        sys.refresh_cpu();
-        sys.refresh_memory();
-        sys.refresh_components();
+        let config = match cosmic_config::Config::new(Self::APP_ID, CONFIG_VERSION) {
+            Ok(handler) => Config::get_entry(&handler).unwrap_or_else(|(errs, config)| {
+                for err in errs {
+                    tracing::error!(?err, "Error loading config");
+                }
+                config
+            }),
+            Err(err) => {
+                tracing::error!(?err, "Error creating config handler");
+                Config::default()
+            }
+        };
+
+        let sys = System::new_with_specifics(Self::refresh_kind(&config));
+
+        let (prev_rx, prev_tx) = sys
+            .networks()
+            .iter()
+            .filter(|(name, _)| Self::interface_enabled(&config, name))
+            .fold((0u64, 0u64), |(rx, tx), (_, data)| {
+                (rx + data.total_received(), tx + data.total_transmitted())
+            });
 
         let mut window = Self {
             core,
+            popup: None,
             sys,
+            config,
             cpu_usage: 0.0,
             avg_freq: 0,
             cpu_temp: 0.0,
             ram_percent: 0.0,
+            per_core: Vec::new(),
+            cpu_history: VecDeque::new(),
+            ram_history: VecDeque::new(),
+            temp_history: VecDeque::new(),
+            net_rx_rate: 0.0,
+            net_tx_rate: 0.0,
+            prev_rx,
+            prev_tx,
+            last_instant: Instant::now(),
+            disks: Vec::new(),
         };
 
         window.update_metrics();
@@ -126,12 +477,46 @@ impl cosmic::Application for Window {
     }
 
     fn subscription(&self) -> Subscription<Message> {
-        cosmic::iced::time::every(std::time::Duration::from_secs(1)).map(|_| Message::Tick)
+        Subscription::batch(vec![
+            cosmic::iced::time::every(std::time::Duration::from_millis(
+                self.config.refresh_interval_ms,
+            ))
+            .map(|_| Message::Tick),
+            cosmic_config::config_subscription::<_, Config>(
+                std::any::TypeId::of::<Config>(),
+                Self::APP_ID.into(),
+                CONFIG_VERSION,
+            )
+            .map(|update| Message::ConfigChanged(update.config)),
+        ])
     }
 
-    fn update(&mut self, _message: Message) -> cosmic::iced::Task<app::Message<Self::Message>> {
-        // Only Tick exists, on every tick refresh metrics
-        self.update_metrics();
+    fn update(&mut self, message: Message) -> cosmic::iced::Task<app::Message<Self::Message>> {
+        match message {
+            Message::Tick => self.update_metrics(),
+            Message::ConfigChanged(config) => self.config = config,
+            Message::TogglePopup => {
+                return if let Some(popup) = self.popup.take() {
+                    destroy_popup(popup)
+                } else {
+                    let new_id = Id::unique();
+                    self.popup = Some(new_id);
+                    let mut popup_settings = self.core.applet.get_popup_settings(
+                        self.core.main_window_id().unwrap(),
+                        new_id,
+                        None,
+                        None,
+                        None,
+                    );
+                    popup_settings.positioner.size_limits = Limits::NONE
+                        .max_width(372.0)
+                        .min_width(300.0)
+                        .min_height(200.0)
+                        .max_height(1080.0);
+                    get_popup(popup_settings)
+                };
+            }
+        }
         cosmic::iced::Task::none()
     }
 
@@ -141,37 +526,243 @@ impl cosmic::Application for Window {
             PanelAnchor::Top | PanelAnchor::Bottom
         );
 
-        let content = if horizontal {
-            row![
-                text(format!("CPU {}", Self::format_percent(self.cpu_usage))),
-                text(format!("{}", Self::format_freq(self.avg_freq))),
-                text(format!("{}", Self::format_temp(self.cpu_temp))),
-                text(format!("RAM {}", Self::format_percent(self.ram_percent))),
-            ]
-            .spacing(8)
-            .align_y(Alignment::Center)
-        } else {
-            row![
-                text(format!("CPU {}", Self::format_percent(self.cpu_usage))),
-                text(format!("{}", Self::format_freq(self.avg_freq))),
-                text(format!("{}", Self::format_temp(self.cpu_temp))),
-                text(format!("RAM {}", Self::format_percent(self.ram_percent))),
-            ]
-            .spacing(4)
-            .align_y(Alignment::Center)
-        };
+        let (cpu_usage, cpu_freq) = self.displayed_cpu();
+
+        let mut items = Vec::new();
+        if self.config.show_cpu {
+            items.push(text(format!("CPU {}", Self::format_percent(cpu_usage))).into());
+        }
+        if self.config.show_freq {
+            items.push(text(format!("{}", Self::format_freq(cpu_freq))).into());
+        }
+        if self.config.show_temp {
+            items.push(text(self.format_temp(self.cpu_temp)).into());
+        }
+        if self.config.show_ram {
+            items.push(text(format!("RAM {}", Self::format_percent(self.ram_percent))).into());
+        }
+        if self.config.show_network {
+            items.push(
+                text(format!(
+                    "↓{} ↑{}",
+                    Self::format_rate(self.net_rx_rate),
+                    Self::format_rate(self.net_tx_rate)
+                ))
+                .into(),
+            );
+        }
+        if let Some(disk) = self.summary_disk() {
+            items.push(text(format!("Disk {}", Self::format_percent(disk.percent))).into());
+        }
+
+        let content = row(items)
+            .spacing(if horizontal { 8 } else { 4 })
+            .align_y(Alignment::Center);
 
         let button = button::custom(content)
             .padding([
                 self.core.applet.suggested_padding(horizontal),
                 self.core.applet.suggested_padding(!horizontal),
             ])
-            .class(cosmic::theme::Button::AppletIcon);
+            .class(cosmic::theme::Button::AppletIcon)
+            .on_press(Message::TogglePopup);
 
         autosize::autosize(button, cosmic::widget::Id::unique()).into()
     }
 
+    fn view_window(&self, id: Id) -> Element<'_, Message> {
+        if Some(id) != self.popup {
+            return text("").into();
+        }
+
+        let mut content = column![].spacing(8).padding(16);
+
+        if self.config.show_cpu || self.config.show_freq {
+            content = content.push(text(format!(
+                "CPU average {}",
+                Self::format_percent(self.cpu_usage)
+            )));
+
+            for (i, (usage, freq)) in self.per_core.iter().enumerate() {
+                content = content.push(text(format!(
+                    "Core {i}: {} @ {}",
+                    Self::format_percent(*usage),
+                    Self::format_freq(*freq)
+                )));
+            }
+
+            content = content.push(text("CPU history")).push(sparkline(
+                &self.visible_history(&self.cpu_history),
+                Color::from_rgb(0.3, 0.6, 1.0),
+            ));
+        }
+
+        if self.config.show_ram {
+            content = content.push(text("RAM history")).push(sparkline(
+                &self.visible_history(&self.ram_history),
+                Color::from_rgb(0.4, 0.8, 0.4),
+            ));
+        }
+
+        if self.config.show_temp {
+            content = content.push(text("Temperature history")).push(sparkline(
+                &self.visible_history(&self.temp_history),
+                Color::from_rgb(1.0, 0.5, 0.3),
+            ));
+        }
+
+        if !self.disks.is_empty() {
+            content = content.push(text("Disks"));
+            for disk in &self.disks {
+                content = content.push(text(format!(
+                    "{}: {} / {} ({})",
+                    disk.mount_point,
+                    Self::format_bytes(disk.used),
+                    Self::format_bytes(disk.total),
+                    Self::format_percent(disk.percent)
+                )));
+            }
+        }
+
+        self.core.applet.popup_container(content).into()
+    }
+
     fn on_close_requested(&self, _id: cosmic::iced::window::Id) -> Option<Message> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windowed_tail_clamps_to_history_length() {
+        let history: VecDeque<f32> = vec![1.0, 2.0, 3.0].into_iter().collect();
+        assert_eq!(Window::windowed_tail(&history, 10), vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn windowed_tail_keeps_only_the_most_recent_samples() {
+        let history: VecDeque<f32> = (0..10).map(|i| i as f32).collect();
+        assert_eq!(Window::windowed_tail(&history, 3), vec![7.0, 8.0, 9.0]);
+    }
+
+    #[test]
+    fn convert_temp_celsius_is_passthrough() {
+        assert_eq!(
+            Window::convert_temp(TemperatureUnit::Celsius, 0.0),
+            "0.0 °C"
+        );
+    }
+
+    #[test]
+    fn convert_temp_fahrenheit_matches_known_points() {
+        assert_eq!(
+            Window::convert_temp(TemperatureUnit::Fahrenheit, 0.0),
+            "32.0 °F"
+        );
+        assert_eq!(
+            Window::convert_temp(TemperatureUnit::Fahrenheit, 100.0),
+            "212.0 °F"
+        );
+    }
+
+    #[test]
+    fn convert_temp_kelvin_adds_absolute_zero_offset() {
+        assert_eq!(
+            Window::convert_temp(TemperatureUnit::Kelvin, 0.0),
+            "273.1 K"
+        );
+    }
+
+    #[test]
+    fn format_rate_picks_the_largest_unit_under_the_value() {
+        assert_eq!(Window::format_rate(512.0), "512.0 B/s");
+        assert_eq!(Window::format_rate(2048.0), "2.0 KB/s");
+        assert_eq!(Window::format_rate(5.0 * 1024.0 * 1024.0), "5.0 MB/s");
+    }
+
+    #[test]
+    fn format_rate_caps_at_gb_per_sec() {
+        assert_eq!(
+            Window::format_rate(2.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0),
+            "2048.0 GB/s"
+        );
+    }
+
+    #[test]
+    fn interface_enabled_default_includes_physical_interfaces() {
+        let config = Config::default();
+        assert!(Window::interface_enabled(&config, "eth0"));
+    }
+
+    #[test]
+    fn interface_enabled_honors_include_list() {
+        let mut config = Config::default();
+        config.net_interface_include = vec!["eth*".to_string()];
+        assert!(Window::interface_enabled(&config, "eth0"));
+        assert!(!Window::interface_enabled(&config, "wlan0"));
+    }
+
+    #[test]
+    fn interface_enabled_exclude_wins_over_include() {
+        let mut config = Config::default();
+        config.net_interface_include = vec!["eth*".to_string()];
+        config.net_interface_exclude = vec!["eth0".to_string()];
+        assert!(!Window::interface_enabled(&config, "eth0"));
+    }
+
+    #[test]
+    fn interface_enabled_default_excludes_virtual_interfaces() {
+        let config = Config::default();
+        assert!(!Window::interface_enabled(&config, "virbr0"));
+        assert!(!Window::interface_enabled(&config, "lo"));
+    }
+
+    #[test]
+    fn format_bytes_picks_the_largest_unit_under_the_value() {
+        assert_eq!(Window::format_bytes(512), "512.0 B");
+        assert_eq!(Window::format_bytes(2048), "2.0 KB");
+        assert_eq!(Window::format_bytes(5 * 1024 * 1024), "5.0 MB");
+    }
+
+    #[test]
+    fn disk_enabled_defaults_exclude_boot_and_snap() {
+        let config = Config::default();
+        assert!(!Window::disk_enabled(&config, "/boot"));
+        assert!(!Window::disk_enabled(&config, "/snap/core20"));
+        assert!(Window::disk_enabled(&config, "/home"));
+    }
+
+    #[test]
+    fn disk_enabled_honors_custom_exclude_list() {
+        let mut config = Config::default();
+        config.disk_mount_exclude = vec!["/mnt/*".to_string()];
+        assert!(!Window::disk_enabled(&config, "/mnt/backup"));
+        assert!(Window::disk_enabled(&config, "/"));
+    }
+
+    #[test]
+    fn select_cpu_returns_average_when_enabled() {
+        let per_core = vec![(10.0, 1000), (90.0, 2000)];
+        assert_eq!(
+            Window::select_cpu(true, (50.0, 1500), &per_core),
+            (50.0, 1500)
+        );
+    }
+
+    #[test]
+    fn select_cpu_returns_busiest_core_when_disabled() {
+        let per_core = vec![(10.0, 1000), (90.0, 2000), (40.0, 1500)];
+        assert_eq!(
+            Window::select_cpu(false, (50.0, 1500), &per_core),
+            (90.0, 2000)
+        );
+    }
+
+    #[test]
+    fn select_cpu_falls_back_to_average_with_no_per_core_data() {
+        assert_eq!(Window::select_cpu(false, (50.0, 1500), &[]), (50.0, 1500));
+    }
+}